@@ -5,6 +5,7 @@ pub use pallet::*;
 pub mod types;
 
 use frame_support::ensure;
+use sp_runtime::traits::AtLeast32BitUnsigned;
 use sp_std::vec::Vec;
 use types::*;
 
@@ -22,6 +23,15 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + scale_info::TypeInfo {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Id of an asset class, chosen by the runtime.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen + Default + AtLeast32BitUnsigned;
+
+		/// Max number of `Account`/`Approvals` entries removed in a single call to
+		/// `destroy_accounts`/`destroy_approvals`, so that a large asset can be torn down
+		/// without a single call busting the block weight.
+		#[pallet::constant]
+		type RemoveKeyLimit: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -35,7 +45,7 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn asset)]
 	/// Details of an asset.
-	pub(super) type Asset<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, AssetDetails<T>>;
+	pub(super) type Asset<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, AssetDetails<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn account)]
@@ -43,7 +53,7 @@ pub mod pallet {
 	pub(super) type Account<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		AssetId,
+		T::AssetId,
 		Blake2_128Concat,
 		T::AccountId,
 		u128,
@@ -54,12 +64,38 @@ pub mod pallet {
 	#[pallet::getter(fn metadata)]
 	/// Details of an asset.
 	pub(super) type Metadata<T: Config> =
-		StorageMap<_, Blake2_128Concat, AssetId, types::AssetMetadata>;
+		StorageMap<_, Blake2_128Concat, T::AssetId, types::AssetMetadata>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn nonce)]
 	/// Nonce for id of the next created asset
-	pub(super) type Nonce<T: Config> = StorageValue<_, AssetId, ValueQuery>;
+	pub(super) type Nonce<T: Config> = StorageValue<_, T::AssetId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	/// The amount a delegate is allowed to transfer out of an owner's account.
+	pub(super) type Approvals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		u128,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn frozen)]
+	/// Whether a specific account is frozen for a specific asset.
+	pub(super) type Frozen<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -69,33 +105,52 @@ pub mod pallet {
 		/// New asset created
 		Created {
 			owner: T::AccountId,
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 		},
 		/// New metadata has been set for an asset
 		MetadataSet {
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			name: Vec<u8>,
 			symbol: Vec<u8>,
 		},
 		/// Some assets have been minted
 		Minted {
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			owner: T::AccountId,
 			total_supply: u128,
 		},
 		/// Some assets have been burned
 		Burned {
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			owner: T::AccountId,
 			total_supply: u128,
 		},
 		/// Some assets have been transferred
 		Transferred {
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			from: T::AccountId,
 			to: T::AccountId,
 			amount: u128,
 		},
+		/// An account has approved another account to spend on its behalf
+		Approval {
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			amount: u128,
+		},
+		/// An asset has started the destruction process
+		DestructionStarted { asset_id: T::AssetId },
+		/// An asset and all of its metadata has been destroyed
+		Destroyed { asset_id: T::AssetId },
+		/// An account has been frozen for an asset
+		Frozen { asset_id: T::AssetId, who: T::AccountId },
+		/// An account has been thawed for an asset
+		Thawed { asset_id: T::AssetId, who: T::AccountId },
+		/// An asset has been frozen
+		AssetFrozen { asset_id: T::AssetId },
+		/// An asset has been thawed
+		AssetThawed { asset_id: T::AssetId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -105,6 +160,16 @@ pub mod pallet {
 		Unknown,
 		/// The signing account has no permision to do the operation
 		NoPermission,
+		/// The delegate does not have enough allowance to perform the transfer
+		InsufficientAllowance,
+		/// The asset is being destroyed and no longer accepts mints or transfers
+		AssetNotLive,
+		/// The asset still has live `Account` or `Approvals` entries
+		InUse,
+		/// The asset has not started the destruction process
+		NotDestroying,
+		/// The account or the asset is frozen
+		Frozen,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -113,11 +178,11 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(0)]
-		pub fn create(origin: OriginFor<T>) -> DispatchResult {
+		pub fn create(origin: OriginFor<T>, min_balance: u128) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 
 			let id = Self::nonce();
-			let details = AssetDetails::new(origin.clone());
+			let details = AssetDetails::new(origin.clone(), min_balance);
 
 			Asset::<T>::insert(id, details);
 			Nonce::<T>::set(id.saturating_add(1));
@@ -133,7 +198,7 @@ pub mod pallet {
 		#[pallet::weight(0)]
 		pub fn set_metadata(
 			origin: OriginFor<T>,
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			name: Vec<u8>,
 			symbol: Vec<u8>,
 		) -> DispatchResult {
@@ -155,7 +220,7 @@ pub mod pallet {
 		#[pallet::weight(0)]
 		pub fn mint(
 			origin: OriginFor<T>,
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			amount: u128,
 			to: T::AccountId,
 		) -> DispatchResult {
@@ -164,21 +229,33 @@ pub mod pallet {
 
 			let mut total_supply = 0;
 			let mut minted_amount = 0;
+			let mut min_balance = 0;
 
 			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
 				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(details.status == AssetStatus::Live, Error::<T>::AssetNotLive);
 
 				let old_supply = details.supply;
 				details.supply = details.supply.saturating_add(amount);
 				total_supply = details.supply;
 				minted_amount = details.supply - old_supply;
+				min_balance = details.min_balance;
 
 				Ok(())
 			})?;
 
-			Account::<T>::mutate(asset_id, to.clone(), |balance| {
-				*balance += minted_amount;
-			});
+			let was_holder = Self::account(asset_id, &to) > 0 || minted_amount > 0;
+			Self::credit(asset_id, &to, minted_amount);
+
+			let dust = Self::reap_if_dust(asset_id, &to, min_balance, was_holder);
+			if dust > 0 {
+				Asset::<T>::mutate(asset_id, |maybe_details| {
+					if let Some(details) = maybe_details {
+						details.supply = details.supply.saturating_sub(dust);
+						total_supply = details.supply;
+					}
+				});
+			}
 
 			Self::deposit_event(Event::<T>::Minted {
 				asset_id,
@@ -190,28 +267,44 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(0)]
-		pub fn burn(origin: OriginFor<T>, asset_id: AssetId, amount: u128) -> DispatchResult {
+		pub fn burn(origin: OriginFor<T>, asset_id: T::AssetId, amount: u128) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
+			Self::ensure_not_frozen(asset_id, &origin)?;
 
 			let mut total_supply = 0;
+			let mut min_balance = 0;
+			let mut was_holder = false;
 
 			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
 				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(!details.is_frozen, Error::<T>::Frozen);
 
 				let mut burned_amount = 0;
 
 				Account::<T>::mutate(asset_id, origin.clone(), |balance| {
 					let old_balance = *balance;
+					was_holder = old_balance > 0;
 					*balance = balance.saturating_sub(amount);
 					burned_amount = old_balance - *balance;
 				});
 
 				details.supply -= burned_amount;
 				total_supply = details.supply;
+				min_balance = details.min_balance;
 
 				Ok(())
 			})?;
 
+			let dust = Self::reap_if_dust(asset_id, &origin, min_balance, was_holder);
+			if dust > 0 {
+				Asset::<T>::mutate(asset_id, |maybe_details| {
+					if let Some(details) = maybe_details {
+						details.supply = details.supply.saturating_sub(dust);
+						total_supply = details.supply;
+					}
+				});
+			}
+
 			Self::deposit_event(Event::<T>::Burned {
 				asset_id,
 				owner: origin,
@@ -224,43 +317,350 @@ pub mod pallet {
 		#[pallet::weight(0)]
 		pub fn transfer(
 			origin: OriginFor<T>,
-			asset_id: AssetId,
+			asset_id: T::AssetId,
 			amount: u128,
 			to: T::AccountId,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 
-			ensure!(Self::asset(asset_id).is_some(), Error::<T>::Unknown);
+			let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(details.status == AssetStatus::Live, Error::<T>::AssetNotLive);
+			ensure!(!details.is_frozen, Error::<T>::Frozen);
+			Self::ensure_not_frozen(asset_id, &origin)?;
 
 			let mut transfered_amount = 0;
+			let mut source_was_holder = false;
 
 			Account::<T>::mutate(asset_id, origin.clone(), |balance| {
 				let old_balance = *balance;
+				source_was_holder = old_balance > 0;
 				*balance = balance.saturating_sub(amount);
 				transfered_amount = old_balance - *balance;
 			});
 
-			Account::<T>::mutate(asset_id, to.clone(), |balance| {
-				*balance = balance.saturating_add(transfered_amount);
-			});
+			let source_dust =
+				Self::reap_if_dust(asset_id, &origin, details.min_balance, source_was_holder);
+			let credited_amount = transfered_amount.saturating_add(source_dust);
+			let dest_was_holder = Self::account(asset_id, &to) > 0 || credited_amount > 0;
+			Self::credit(asset_id, &to, credited_amount);
+
+			let dest_dust =
+				Self::reap_if_dust(asset_id, &to, details.min_balance, dest_was_holder);
+			if dest_dust > 0 {
+				Asset::<T>::mutate(asset_id, |maybe_details| {
+					if let Some(details) = maybe_details {
+						details.supply = details.supply.saturating_sub(dest_dust);
+					}
+				});
+			}
 
 			Self::deposit_event(Event::<T>::Transferred {
 				asset_id,
 				from: origin,
 				to,
+				amount: transfered_amount,
+			});
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			delegate: T::AccountId,
+			amount: u128,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(Self::asset(asset_id).is_some(), Error::<T>::Unknown);
+
+			Approvals::<T>::mutate(asset_id, (owner.clone(), delegate.clone()), |allowance| {
+				*allowance = allowance.saturating_add(amount);
+			});
+
+			Self::deposit_event(Event::<T>::Approval {
+				asset_id,
+				owner,
+				delegate,
 				amount,
 			});
 
 			Ok(())
 		}
+
+		#[pallet::weight(0)]
+		pub fn transfer_approved(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			to: T::AccountId,
+			amount: u128,
+		) -> DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(details.status == AssetStatus::Live, Error::<T>::AssetNotLive);
+			ensure!(!details.is_frozen, Error::<T>::Frozen);
+			Self::ensure_not_frozen(asset_id, &owner)?;
+
+			Approvals::<T>::try_mutate(
+				asset_id,
+				(owner.clone(), delegate),
+				|allowance| -> DispatchResult {
+					ensure!(*allowance >= amount, Error::<T>::InsufficientAllowance);
+					*allowance -= amount;
+					Ok(())
+				},
+			)?;
+
+			let mut transfered_amount = 0;
+			let mut source_was_holder = false;
+
+			Account::<T>::mutate(asset_id, owner.clone(), |balance| {
+				let old_balance = *balance;
+				source_was_holder = old_balance > 0;
+				*balance = balance.saturating_sub(amount);
+				transfered_amount = old_balance - *balance;
+			});
+
+			let source_dust =
+				Self::reap_if_dust(asset_id, &owner, details.min_balance, source_was_holder);
+			let credited_amount = transfered_amount.saturating_add(source_dust);
+			let dest_was_holder = Self::account(asset_id, &to) > 0 || credited_amount > 0;
+			Self::credit(asset_id, &to, credited_amount);
+
+			let dest_dust =
+				Self::reap_if_dust(asset_id, &to, details.min_balance, dest_was_holder);
+			if dest_dust > 0 {
+				Asset::<T>::mutate(asset_id, |maybe_details| {
+					if let Some(details) = maybe_details {
+						details.supply = details.supply.saturating_sub(dest_dust);
+					}
+				});
+			}
+
+			Self::deposit_event(Event::<T>::Transferred {
+				asset_id,
+				from: owner,
+				to,
+				amount: transfered_amount,
+			});
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			delegate: T::AccountId,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Approvals::<T>::remove(asset_id, (owner, delegate));
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn start_destroy(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_is_owner(asset_id, origin)?;
+
+			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.status = AssetStatus::Destroying;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::DestructionStarted { asset_id });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn destroy_accounts(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T>::NotDestroying);
+
+			let limit = T::RemoveKeyLimit::get() as usize;
+			let removed: Vec<(T::AccountId, u128)> =
+				Account::<T>::iter_prefix(asset_id).take(limit).collect();
+
+			for (who, _) in removed.iter() {
+				Account::<T>::remove(asset_id, who.clone());
+			}
+
+			let removed_supply: u128 = removed.iter().map(|(_, balance)| balance).sum();
+			let removed_accounts = removed.len() as u64;
+
+			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.supply = details.supply.saturating_sub(removed_supply);
+				details.accounts = details.accounts.saturating_sub(removed_accounts as u32);
+				Ok(())
+			})?;
+
+			Ok(Some(T::DbWeight::get().reads_writes(1, removed_accounts + 1)).into())
+		}
+
+		#[pallet::weight(0)]
+		pub fn destroy_approvals(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+
+			let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T>::NotDestroying);
+
+			let limit = T::RemoveKeyLimit::get() as usize;
+			let removed: Vec<(T::AccountId, T::AccountId)> = Approvals::<T>::iter_prefix(asset_id)
+				.take(limit)
+				.map(|(owner_delegate, _)| owner_delegate)
+				.collect();
+
+			for owner_delegate in removed.iter() {
+				Approvals::<T>::remove(asset_id, owner_delegate.clone());
+			}
+
+			let removed_approvals = removed.len() as u64;
+
+			Ok(Some(T::DbWeight::get().writes(removed_approvals)).into())
+		}
+
+		#[pallet::weight(0)]
+		pub fn finish_destroy(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T>::NotDestroying);
+			ensure!(Account::<T>::iter_prefix(asset_id).next().is_none(), Error::<T>::InUse);
+			ensure!(Approvals::<T>::iter_prefix(asset_id).next().is_none(), Error::<T>::InUse);
+
+			Asset::<T>::remove(asset_id);
+			Metadata::<T>::remove(asset_id);
+
+			Self::deposit_event(Event::<T>::Destroyed { asset_id });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn freeze(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_is_owner(asset_id, origin)?;
+
+			Frozen::<T>::insert(asset_id, who.clone(), true);
+
+			Self::deposit_event(Event::<T>::Frozen { asset_id, who });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn thaw(origin: OriginFor<T>, asset_id: T::AssetId, who: T::AccountId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_is_owner(asset_id, origin)?;
+
+			Frozen::<T>::remove(asset_id, who.clone());
+
+			Self::deposit_event(Event::<T>::Thawed { asset_id, who });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn freeze_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_is_owner(asset_id, origin)?;
+
+			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.is_frozen = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::AssetFrozen { asset_id });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn thaw_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_is_owner(asset_id, origin)?;
+
+			Asset::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				details.is_frozen = false;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::AssetThawed { asset_id });
+
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn ensure_is_owner(asset_id: AssetId, account: T::AccountId) -> Result<(), Error<T>> {
+	fn ensure_is_owner(asset_id: T::AssetId, account: T::AccountId) -> Result<(), Error<T>> {
 		let details = Self::asset(asset_id).ok_or(Error::<T>::Unknown)?;
 		ensure!(details.owner == account, Error::<T>::NoPermission);
 
 		Ok(())
 	}
+
+	fn ensure_not_frozen(asset_id: T::AssetId, who: &T::AccountId) -> Result<(), Error<T>> {
+		ensure!(!Self::frozen(asset_id, who), Error::<T>::Frozen);
+
+		Ok(())
+	}
+
+	/// Credits `amount` to `who`'s balance for `asset_id`, bumping the live-holder count
+	/// if the account previously held nothing.
+	fn credit(asset_id: T::AssetId, who: &T::AccountId, amount: u128) {
+		let was_empty = Self::account(asset_id, who) == 0;
+
+		Account::<T>::mutate(asset_id, who, |balance| {
+			*balance = balance.saturating_add(amount);
+		});
+
+		if was_empty && amount > 0 {
+			Asset::<T>::mutate(asset_id, |maybe_details| {
+				if let Some(details) = maybe_details {
+					details.accounts = details.accounts.saturating_add(1);
+				}
+			});
+		}
+	}
+
+	/// Removes `who`'s `Account` entry for `asset_id` if its balance has fallen to zero or below
+	/// the asset's minimum balance, returning the dust removed. `was_holder` must reflect whether
+	/// `who` was already a counted holder going into the operation that led here (or was just
+	/// credited a non-zero amount by it); otherwise an account that never held any balance
+	/// would be reaped and wrongly decrement `details.accounts`.
+	fn reap_if_dust(asset_id: T::AssetId, who: &T::AccountId, min_balance: u128, was_holder: bool) -> u128 {
+		if !was_holder {
+			return 0;
+		}
+
+		let balance = Self::account(asset_id, who);
+		if balance > 0 && balance >= min_balance {
+			return 0;
+		}
+
+		Account::<T>::remove(asset_id, who);
+		Asset::<T>::mutate(asset_id, |maybe_details| {
+			if let Some(details) = maybe_details {
+				details.accounts = details.accounts.saturating_sub(1);
+			}
+		});
+
+		balance
+	}
 }
\ No newline at end of file
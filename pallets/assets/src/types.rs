@@ -0,0 +1,57 @@
+use frame_support::pallet_prelude::*;
+use sp_std::vec::Vec;
+
+use crate::pallet::Config;
+
+/// The lifecycle stage of an asset class.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum AssetStatus {
+	/// The asset is live and can be minted, transferred and burned.
+	Live,
+	/// The asset is in the process of being destroyed.
+	Destroying,
+}
+
+/// Details of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct AssetDetails<T: Config> {
+	/// Account that owns the asset class.
+	pub owner: T::AccountId,
+	/// Total supply in circulation.
+	pub supply: u128,
+	/// Lifecycle stage of the asset.
+	pub status: AssetStatus,
+	/// Whether the whole asset class is frozen for transfers.
+	pub is_frozen: bool,
+	/// The minimum balance an account must hold to keep its `Account` entry alive.
+	pub min_balance: u128,
+	/// The number of accounts that currently hold a non-zero balance of this asset.
+	pub accounts: u32,
+}
+
+impl<T: Config> AssetDetails<T> {
+	pub fn new(owner: T::AccountId, min_balance: u128) -> Self {
+		Self {
+			owner,
+			supply: 0,
+			status: AssetStatus::Live,
+			is_frozen: false,
+			min_balance,
+			accounts: 0,
+		}
+	}
+}
+
+/// Metadata of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AssetMetadata {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+}
+
+impl AssetMetadata {
+	pub fn new(name: Vec<u8>, symbol: Vec<u8>) -> Self {
+		Self { name, symbol }
+	}
+}
@@ -0,0 +1,168 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+pub mod types;
+
+use frame_support::ensure;
+use support::Sellable;
+use types::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_support::traits::{Currency, ExistenceRequirement};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::SaturatedConversion;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + scale_info::TypeInfo {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used to pay for listed assets.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Id of the assets that can be listed on this marketplace.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// The asset ledger this marketplace sells out of, without depending on its crate.
+		type Nft: Sellable<Self::AccountId, Self::AssetId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn listing)]
+	/// Details of a listing.
+	pub(super) type Listings<T: Config> = StorageMap<_, Blake2_128Concat, ListingId, Listing<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn nonce)]
+	/// Nonce for id of the next created listing
+	pub(super) type Nonce<T: Config> = StorageValue<_, ListingId, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new listing has been created
+		Listed {
+			listing_id: ListingId,
+			seller: T::AccountId,
+			asset_id: T::AssetId,
+			amount: u128,
+			price_per_unit: BalanceOf<T>,
+		},
+		/// A listing has been withdrawn
+		Unlisted { listing_id: ListingId },
+		/// Some units of a listing have been sold
+		Sold {
+			listing_id: ListingId,
+			buyer: T::AccountId,
+			amount: u128,
+		},
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The listing ID is unknown
+		Unknown,
+		/// The signing account has no permision to do the operation
+		NoPermission,
+		/// The seller does not hold enough of the asset to create or honour the listing, or the
+		/// listing has fewer units left than requested
+		InsufficientAmount,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(0)]
+		pub fn list(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			amount: u128,
+			price_per_unit: BalanceOf<T>,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+
+			ensure!(
+				T::Nft::amount_owned(asset_id, seller.clone()) >= amount,
+				Error::<T>::InsufficientAmount
+			);
+
+			let id = Self::nonce();
+			Nonce::<T>::set(id.saturating_add(1));
+
+			Listings::<T>::insert(id, Listing::new(seller.clone(), asset_id, amount, price_per_unit));
+
+			Self::deposit_event(Event::<T>::Listed {
+				listing_id: id,
+				seller,
+				asset_id,
+				amount,
+				price_per_unit,
+			});
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn unlist(origin: OriginFor<T>, listing_id: ListingId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let listing = Self::listing(listing_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(listing.seller == who, Error::<T>::NoPermission);
+
+			Listings::<T>::remove(listing_id);
+
+			Self::deposit_event(Event::<T>::Unlisted { listing_id });
+
+			Ok(())
+		}
+
+		#[pallet::weight(0)]
+		pub fn buy(origin: OriginFor<T>, listing_id: ListingId, amount: u128) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let mut listing = Self::listing(listing_id).ok_or(Error::<T>::Unknown)?;
+			ensure!(listing.amount >= amount, Error::<T>::InsufficientAmount);
+			ensure!(
+				T::Nft::amount_owned(listing.asset_id, listing.seller.clone()) >= amount,
+				Error::<T>::InsufficientAmount
+			);
+
+			let price = listing.price_per_unit.saturating_mul(amount.saturated_into());
+			T::Currency::transfer(
+				&buyer,
+				&listing.seller,
+				price,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let moved = T::Nft::transfer(listing.asset_id, listing.seller.clone(), buyer.clone(), amount);
+
+			listing.amount = listing.amount.saturating_sub(moved);
+			if listing.amount == 0 {
+				Listings::<T>::remove(listing_id);
+			} else {
+				Listings::<T>::insert(listing_id, listing);
+			}
+
+			Self::deposit_event(Event::<T>::Sold {
+				listing_id,
+				buyer,
+				amount: moved,
+			});
+
+			Ok(())
+		}
+	}
+}
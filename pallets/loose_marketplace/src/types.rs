@@ -0,0 +1,36 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::Currency;
+
+use crate::pallet::Config;
+
+/// Id of a listing.
+pub type ListingId = u32;
+
+/// The `Currency` balance type used by this pallet's `Config`.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A listing of `amount` units of `asset_id`, offered by `seller` at `price_per_unit`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Listing<T: Config> {
+	/// Account that created the listing and will receive payment.
+	pub seller: T::AccountId,
+	/// The asset class being sold.
+	pub asset_id: T::AssetId,
+	/// How many units are still for sale.
+	pub amount: u128,
+	/// Price paid per unit purchased.
+	pub price_per_unit: BalanceOf<T>,
+}
+
+impl<T: Config> Listing<T> {
+	pub fn new(
+		seller: T::AccountId,
+		asset_id: T::AssetId,
+		amount: u128,
+		price_per_unit: BalanceOf<T>,
+	) -> Self {
+		Self { seller, asset_id, amount, price_per_unit }
+	}
+}
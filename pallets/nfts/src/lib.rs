@@ -11,8 +11,12 @@ pub mod types;
 
 use frame_support::ensure;
 use sp_std::vec::Vec;
+use support::Sellable;
 use types::*;
 
+use codec::Encode;
+use sp_runtime::traits::{AtLeast32BitUnsigned, IdentifyAccount, Verify};
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -22,17 +26,26 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config + scale_info::TypeInfo {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Id of a unique asset class, chosen by the runtime.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen + Default + AtLeast32BitUnsigned;
+
+		/// The public key type used to verify a `PreSignedMint`'s signature.
+		type Public: IdentifyAccount<AccountId = Self::AccountId> + Parameter;
+
+		/// The signature type used to authorize a `PreSignedMint` off-chain.
+		type Signature: Verify<Signer = Self::Public> + Parameter;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
-	/// Is mapping UniqueAssetId with UniqueAssetDetails
+	/// Is mapping the asset id with UniqueAssetDetails
 	#[pallet::storage]
 	#[pallet::getter(fn unique_asset)]
 	pub(super) type UniqueAsset<T: Config> =
-		StorageMap<_, Blake2_128Concat, UniqueAssetId, UniqueAssetDetails<T>>;
+		StorageMap<_, Blake2_128Concat, T::AssetId, UniqueAssetDetails<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn account)]
@@ -40,7 +53,7 @@ pub mod pallet {
 	pub(super) type Account<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		UniqueAssetId,
+		T::AssetId,
 		Blake2_128Concat,
 		T::AccountId,
 		u128,
@@ -50,17 +63,33 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn nonce)]
 	/// Nonce for id of the next created asset
-	pub(super) type Nonce<T: Config> = StorageValue<_, UniqueAssetId, ValueQuery>;
+	pub(super) type Nonce<T: Config> = StorageValue<_, T::AssetId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn used_pre_signed_mint)]
+	/// Nonces of `PreSignedMint`s already claimed by a given signer, so that each can only be
+	/// minted once.
+	pub(super) type UsedPreSignedMints<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		u64,
+		bool,
+		ValueQuery,
+	>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// New unique asset created
-		Created { creator: T::AccountId, asset_id: UniqueAssetId },
+		Created { creator: T::AccountId, asset_id: T::AssetId },
 		/// Some assets have been burned
-		Burned { asset_id: UniqueAssetId, owner: T::AccountId, total_supply: u128 },
+		Burned { asset_id: T::AssetId, owner: T::AccountId, total_supply: u128 },
 		/// Some assets have been transferred
-		Transferred { asset_id: UniqueAssetId, from: T::AccountId, to: T::AccountId, amount: u128 },
+		Transferred { asset_id: T::AssetId, from: T::AccountId, to: T::AccountId, amount: u128 },
+		/// An asset has been minted from a pre-signed mint claimed by someone else
+		PreSignedMinted { asset_id: T::AssetId, claimer: T::AccountId, mint_to: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -73,6 +102,12 @@ pub mod pallet {
 		NoSupply,
 		/// Type overflow
 		TypeOverflow,
+		/// The pre-signed mint's deadline has passed
+		Expired,
+		/// The signature does not match the claimed signer
+		InvalidSignature,
+		/// This pre-signed mint has already been claimed
+		AlreadyClaimed,
 	}
 
 	#[pallet::call]
@@ -107,7 +142,7 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(0)]
-		pub fn burn(origin: OriginFor<T>, asset_id: UniqueAssetId, amount: u128) -> DispatchResult {
+		pub fn burn(origin: OriginFor<T>, asset_id: T::AssetId, amount: u128) -> DispatchResult {
 			// Ensure call is signed
 			let who = ensure_signed(origin)?;
 
@@ -115,10 +150,10 @@ pub mod pallet {
 			ensure!(Self::unique_asset(asset_id).is_some(), Error::<T>::Unknown);
 
 			// Ensure own some
-			ensure!(Self::account(0, who.clone()) > 0, Error::<T>::NotOwned);
+			ensure!(Self::account(asset_id, who.clone()) > 0, Error::<T>::NotOwned);
 
 			// Handle situation where origin is transfering more than his amount
-			let origin_amount = Self::account(0, who.clone());
+			let origin_amount = Self::account(asset_id, who.clone());
 			let mut new_amount = amount;
 			if amount > origin_amount {
 				new_amount = origin_amount;
@@ -151,7 +186,7 @@ pub mod pallet {
 		#[pallet::weight(0)]
 		pub fn transfer(
 			origin: OriginFor<T>,
-			asset_id: UniqueAssetId,
+			asset_id: T::AssetId,
 			amount: u128,
 			to: T::AccountId,
 		) -> DispatchResult {
@@ -162,10 +197,10 @@ pub mod pallet {
 			ensure!(Self::unique_asset(asset_id).is_some(), Error::<T>::Unknown);
 
 			// Ensure own some
-			ensure!(Self::account(0, who.clone()) > 0, Error::<T>::NotOwned);
+			ensure!(Self::account(asset_id, who.clone()) > 0, Error::<T>::NotOwned);
 
 			// Handle situation where origin is transfering more than his amount
-			let origin_amount = Self::account(0, who.clone());
+			let origin_amount = Self::account(asset_id, who.clone());
 			let mut new_amount = amount;
 			if amount > origin_amount {
 				new_amount = origin_amount;
@@ -188,5 +223,81 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		#[pallet::weight(0)]
+		pub fn mint_pre_signed(
+			origin: OriginFor<T>,
+			mint_data: PreSignedMint<T>,
+			signature: T::Signature,
+			signer: T::Public,
+		) -> DispatchResult {
+			// Ensure call is signed by the claimer, who pays the on-chain fee
+			let claimer = ensure_signed(origin)?;
+
+			// Ensure the pre-signed mint has not expired
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= mint_data.deadline,
+				Error::<T>::Expired
+			);
+
+			// Ensure the signature is valid for the claimed signer
+			let creator = signer.clone().into_account();
+			ensure!(
+				signature.verify(&*mint_data.encode(), &creator),
+				Error::<T>::InvalidSignature
+			);
+
+			// Ensure this pre-signed mint has not already been claimed
+			ensure!(
+				!Self::used_pre_signed_mint(creator.clone(), mint_data.nonce),
+				Error::<T>::AlreadyClaimed
+			);
+			<UsedPreSignedMints<T>>::insert(creator.clone(), mint_data.nonce, true);
+
+			// Increments nonce for next ids
+			let id = Self::nonce();
+			<Nonce<T>>::set(Self::nonce().checked_add(1).ok_or(Error::<T>::TypeOverflow)?);
+
+			// Generates asset details
+			let asset_details =
+				UniqueAssetDetails::new(creator.clone(), mint_data.asset_metadata, mint_data.supply);
+
+			// Stores unique asset
+			<UniqueAsset<T>>::insert(id, asset_details);
+
+			// Stores Account
+			<Account<T>>::insert(id, mint_data.mint_to.clone(), mint_data.supply);
+
+			// Emmit events
+			Self::deposit_event(Event::Created { creator, asset_id: id });
+			Self::deposit_event(Event::PreSignedMinted {
+				asset_id: id,
+				claimer,
+				mint_to: mint_data.mint_to,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Sellable<T::AccountId, T::AssetId> for Pallet<T> {
+	fn amount_owned(id: T::AssetId, account: T::AccountId) -> u128 {
+		Self::account(id, account)
+	}
+
+	fn transfer(id: T::AssetId, from: T::AccountId, to: T::AccountId, amount: u128) -> u128 {
+		let owned = Self::account(id, from.clone());
+		let moved = owned.min(amount);
+
+		Account::<T>::mutate(id, from, |balance| {
+			*balance -= moved;
+		});
+
+		Account::<T>::mutate(id, to, |balance| {
+			*balance += moved;
+		});
+
+		moved
 	}
 }
@@ -0,0 +1,38 @@
+use frame_support::pallet_prelude::*;
+use sp_std::vec::Vec;
+
+use crate::pallet::Config;
+
+/// Details of a unique asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct UniqueAssetDetails<T: Config> {
+	/// Account that created the asset class.
+	pub creator: T::AccountId,
+	/// Arbitrary metadata describing the asset class.
+	pub metadata: Vec<u8>,
+	/// Total supply in circulation.
+	pub supply: u128,
+}
+
+impl<T: Config> UniqueAssetDetails<T> {
+	pub fn new(creator: T::AccountId, metadata: Vec<u8>, supply: u128) -> Self {
+		Self { creator, metadata, supply }
+	}
+}
+
+/// A mint authorized off-chain by its creator, to be claimed on-chain by whoever pays the fee.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct PreSignedMint<T: Config> {
+	/// Arbitrary metadata describing the asset class being minted.
+	pub asset_metadata: Vec<u8>,
+	/// Total supply to mint.
+	pub supply: u128,
+	/// Block after which the mint can no longer be claimed.
+	pub deadline: T::BlockNumber,
+	/// Account that will receive the minted supply.
+	pub mint_to: T::AccountId,
+	/// Chosen by the creator to make this pre-signed mint claimable only once.
+	pub nonce: u64,
+}